@@ -0,0 +1,140 @@
+//! A Snakes and Ladders board: its size and the snake/ladder jumps from
+//! one square to another.
+
+use std::collections::HashMap;
+
+/// Size of the classic board, and the default size used when generating
+/// a random one.
+pub const DEFAULT_SIZE: i32 = 100;
+
+
+/// A board layout: its size, and a map from every snake-head or
+/// ladder-foot square to where it really lands.
+pub struct Board {
+    size: i32,
+    jumps: HashMap<i32, i32>,
+}
+
+impl Board {
+    /// Build a board, validating that every jump lands in range, that no
+    /// square is both an origin and a destination, and that no jump is a
+    /// no-op.
+    pub fn new(size: i32, jumps: HashMap<i32, i32>) -> Self {
+        for (&from, &to) in &jumps {
+            assert!((1..=size).contains(&from), "jump from square {} is out of range", from);
+            assert!((1..=size).contains(&to), "jump to square {} is out of range", to);
+            assert_ne!(from, to, "square {} jumps to itself", from);
+            assert!(!jumps.contains_key(&to), "square {} is both a jump origin and destination", to);
+        }
+
+        Board { size, jumps }
+    }
+
+    /// The classic 100-square Snakes and Ladders board.
+    pub fn classic() -> Self {
+        let jumps = HashMap::from([
+            // Ladders
+            (1, 38), (4, 14), (9, 31), (21, 42), (28, 84), (36, 44), (51, 67), (71, 91), (80, 100),
+            // Snakes
+            (98, 78), (95, 75), (93, 73), (87, 24), (64, 60), (62, 19), (56, 53), (49, 11), (48, 26), (16, 6),
+        ]);
+
+        Board::new(DEFAULT_SIZE, jumps)
+    }
+
+    /// Scatter `num_snakes` snakes and `num_ladders` ladders across a
+    /// board of the given size, for quick board-randomisation
+    /// experiments. Origins are drawn from a shuffled list of interior
+    /// squares, so no two jumps ever share an origin or destination.
+    ///
+    /// `seed` drives the shuffle directly (rather than fastrand's
+    /// unseeded global generator), so a randomised board is just as
+    /// replayable from `--seed` as everything else in a run.
+    pub fn random(size: i32, num_snakes: usize, num_ladders: usize, seed: u64) -> Self {
+        let mut squares: Vec<i32> = (2..size).collect(); // never the start or finish square
+        fastrand::Rng::with_seed(seed).shuffle(&mut squares);
+
+        let total = num_snakes + num_ladders;
+        assert!(squares.len() >= 2 * total, "board is too small for this many snakes and ladders");
+
+        let mut jumps = HashMap::new();
+        let mut pairs = squares.chunks_exact(2).take(total);
+
+        for _ in 0..num_ladders {
+            let pair = pairs.next().unwrap();
+            jumps.insert(pair[0].min(pair[1]), pair[0].max(pair[1]));
+        }
+        for _ in 0..num_snakes {
+            let pair = pairs.next().unwrap();
+            jumps.insert(pair[0].max(pair[1]), pair[0].min(pair[1]));
+        }
+
+        Board::new(size, jumps)
+    }
+
+    pub fn size(&self) -> i32 {
+        self.size
+    }
+
+    /// Where landing on `square` really ends up, following any snake or
+    /// ladder starting there.
+    pub fn resolve(&self, square: i32) -> i32 {
+        *self.jumps.get(&square).unwrap_or(&square)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_board_resolves_known_jumps() {
+        let board = Board::classic();
+        assert_eq!(board.resolve(1), 38); // ladder
+        assert_eq!(board.resolve(98), 78); // snake
+        assert_eq!(board.resolve(50), 50); // plain square
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn rejects_jump_origin_out_of_range() {
+        Board::new(100, HashMap::from([(101, 50)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn rejects_jump_destination_out_of_range() {
+        Board::new(100, HashMap::from([(50, 200)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "jumps to itself")]
+    fn rejects_jump_to_itself() {
+        Board::new(100, HashMap::from([(50, 50)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "both a jump origin and destination")]
+    fn rejects_square_as_both_origin_and_destination() {
+        Board::new(100, HashMap::from([(10, 20), (20, 30)]));
+    }
+
+    #[test]
+    fn random_board_places_the_requested_number_of_jumps() {
+        let board = Board::random(100, 3, 5, 42);
+        assert_eq!(board.jumps.len(), 8);
+        for (&from, &to) in &board.jumps {
+            assert!((2..100).contains(&from));
+            assert!((2..100).contains(&to));
+            assert_ne!(from, to);
+        }
+    }
+
+    #[test]
+    fn random_board_is_reproducible_from_its_seed() {
+        let first = Board::random(100, 3, 5, 42);
+        let second = Board::random(100, 3, 5, 42);
+        assert_eq!(first.jumps, second.jumps);
+    }
+}
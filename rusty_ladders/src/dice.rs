@@ -0,0 +1,218 @@
+//! A configurable six-sided die, weighted or fair, rolled by a
+//! pluggable RNG backend.
+
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+
+
+/// Abstraction over a dice-rolling RNG backend, so different generators
+/// can be benchmarked against each other without recompiling.
+///
+/// Each implementation picks its own way of turning raw bits into a fair
+/// `1..=6` roll (modulo, `gen_range`, or whatever its backend offers),
+/// keeping that tradeoff an implementation detail of the source rather
+/// than of the game loop.
+pub trait DiceSource: RngCore {
+    /// Roll a fair six-sided die, returning a face in `1..=6`.
+    fn roll_1_to_6(&mut self) -> u32;
+}
+
+impl DiceSource for SmallRng {
+    fn roll_1_to_6(&mut self) -> u32 {
+        (self.next_u64() % 6 + 1) as u32 // 176ms for 1e6 games
+    }
+}
+
+
+/// Wraps fastrand's Wyrand generator as a [`DiceSource`].
+pub struct Wyrand(fastrand::Rng);
+
+impl Wyrand {
+    pub fn seeded(seed: u64) -> Self {
+        Wyrand(fastrand::Rng::with_seed(seed))
+    }
+}
+
+impl RngCore for Wyrand {
+    fn next_u32(&mut self) -> u32 {
+        self.0.u32(..)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.u64(..)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl DiceSource for Wyrand {
+    fn roll_1_to_6(&mut self) -> u32 {
+        self.0.u32(1..=6)
+    }
+}
+
+
+/// Selects which [`DiceSource`] backend a run should use.
+#[derive(Clone, Copy)]
+pub enum RngBackendKind {
+    SmallRng,
+    Wyrand,
+}
+
+impl RngBackendKind {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "smallrng" => Some(RngBackendKind::SmallRng),
+            "wyrand" => Some(RngBackendKind::Wyrand),
+            _ => None,
+        }
+    }
+}
+
+
+/// A [`DiceSource`] that can be either backend, chosen at runtime, so
+/// `main` doesn't need to monomorphise the whole simulation per backend.
+pub enum RngBackend {
+    SmallRng(SmallRng),
+    Wyrand(Wyrand),
+}
+
+impl RngBackend {
+    pub fn seeded(kind: RngBackendKind, seed: u64) -> Self {
+        match kind {
+            RngBackendKind::SmallRng => RngBackend::SmallRng(SmallRng::seed_from_u64(seed)),
+            RngBackendKind::Wyrand => RngBackend::Wyrand(Wyrand::seeded(seed)),
+        }
+    }
+}
+
+impl RngCore for RngBackend {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            RngBackend::SmallRng(rng) => rng.next_u32(),
+            RngBackend::Wyrand(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            RngBackend::SmallRng(rng) => rng.next_u64(),
+            RngBackend::Wyrand(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            RngBackend::SmallRng(rng) => rng.fill_bytes(dest),
+            RngBackend::Wyrand(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl DiceSource for RngBackend {
+    fn roll_1_to_6(&mut self) -> u32 {
+        match self {
+            RngBackend::SmallRng(rng) => rng.roll_1_to_6(),
+            RngBackend::Wyrand(rng) => rng.roll_1_to_6(),
+        }
+    }
+}
+
+
+/// A six-sided die whose faces can be weighted unevenly.
+///
+/// Faces are sampled from a discrete distribution built from six weights:
+/// a uniform draw in `[0, total_weight)` is mapped to a face by finding
+/// the first cumulative weight it falls under.
+#[derive(Clone)]
+pub struct WeightedDie {
+    cumulative: [u64; 6],
+    total: u64,
+    uniform: bool,
+}
+
+impl WeightedDie {
+    /// Build a die from six non-negative face weights. A uniform weight
+    /// vector `[1, 1, 1, 1, 1, 1]` reproduces a fair die.
+    pub fn new(weights: [u32; 6]) -> Self {
+        let mut cumulative = [0u64; 6];
+        let mut running = 0u64;
+        for (bucket, &weight) in cumulative.iter_mut().zip(weights.iter()) {
+            running += weight as u64;
+            *bucket = running;
+        }
+        assert!(running > 0, "a die needs at least one face with non-zero weight");
+
+        let uniform = weights.iter().all(|&weight| weight == weights[0]);
+        WeightedDie { cumulative, total: running, uniform }
+    }
+
+    /// A fair die, equivalent to `WeightedDie::new([1, 1, 1, 1, 1, 1])`.
+    pub fn fair() -> Self {
+        Self::new([1; 6])
+    }
+
+    /// Roll the die, returning a face in `1..=6`.
+    ///
+    /// A fair die rolls through the source's own `roll_1_to_6`, so
+    /// backends can be benchmarked on their native fast path; only a
+    /// genuinely loaded die needs the cumulative-weight lookup.
+    pub fn roll(&self, rng: &mut impl DiceSource) -> i32 {
+        if self.uniform {
+            return rng.roll_1_to_6() as i32;
+        }
+
+        let x = rng.next_u64() % self.total;
+        let face = self.cumulative.partition_point(|&cumulative_weight| cumulative_weight <= x);
+        face as i32 + 1
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fair_die_reproduces_the_original_modulo_roll() {
+        let mut expected_rng = SmallRng::seed_from_u64(1234);
+        let mut actual_rng = SmallRng::seed_from_u64(1234);
+        let die = WeightedDie::fair();
+
+        for _ in 0..1000 {
+            let expected = (expected_rng.next_u64() % 6 + 1) as i32;
+            let actual = die.roll(&mut actual_rng);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn loaded_die_only_ever_rolls_weighted_faces() {
+        let die = WeightedDie::new([0, 1, 0, 0, 0, 1]);
+        let mut rng = SmallRng::seed_from_u64(99);
+
+        for _ in 0..1000 {
+            let face = die.roll(&mut rng);
+            assert!(face == 2 || face == 6, "unexpected face {}", face);
+        }
+    }
+
+    #[test]
+    fn rng_backend_kind_parses_known_names_only() {
+        assert!(matches!(RngBackendKind::parse("smallrng"), Some(RngBackendKind::SmallRng)));
+        assert!(matches!(RngBackendKind::parse("wyrand"), Some(RngBackendKind::Wyrand)));
+        assert!(RngBackendKind::parse("mersenne").is_none());
+    }
+}
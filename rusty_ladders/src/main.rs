@@ -1,70 +1,286 @@
 
-//~ use fastrand;
 use rand::prelude::*;
+use std::thread;
+
+mod board;
+mod dice;
+mod stats;
+use board::Board;
+use dice::{DiceSource, RngBackend, RngBackendKind, WeightedDie};
+use stats::Histogram;
+
+
+/// Number of games to simulate in total.
+const NUM_GAMES: u64 = 1_000_000;
+
+/// Number of work-units the game count is split into, each with its own
+/// deterministically-seeded generator. Fixed so the partition (and thus
+/// the aggregate results) never depends on how many threads happen to be
+/// available on the machine running it; threads just divide this fixed
+/// set of units between them.
+const NUM_WORK_UNITS: usize = 256;
+
+/// Hard cap on rolls for a single game. Some loaded dice (e.g. one that
+/// can only roll a number that overshoots the finish from some square)
+/// make a game unwinnable; this stops such a game instead of hanging
+/// the whole run, at the cost of bucketing it as a histogram overflow.
+const MAX_ROLLS_PER_GAME: i32 = 10_000;
 
 
 fn main() {
-    let mut num_rolls = 0;
+    // A `--seed` argument makes the run reproducible; otherwise fall back
+    // to strong default RNG to pick a master seed, then derive one
+    // smaller, faster generator per work-unit from it below.
+    let master_seed = parse_seed_arg().unwrap_or_else(|| rand::thread_rng().next_u64());
+    println!("Using seed: {} (pass --seed {} to reproduce this run's histogram)", master_seed, master_seed);
 
-    // Use strong default RNG to seed faster non-cryptographic generator.
-    // We can then create multiple small RNGs, one per work-unit.
-    let mut thread_rng = rand::thread_rng();
-    let mut rng = SmallRng::from_rng(&mut thread_rng).unwrap();
+    let rng_backend = parse_rng_arg().unwrap_or(RngBackendKind::SmallRng);
+    let die = match parse_weights_arg() {
+        Some(weights) => WeightedDie::new(weights),
+        None => WeightedDie::fair(),
+    };
+    let board = match parse_random_board_arg() {
+        Some((num_snakes, num_ladders)) => {
+            Board::random(board::DEFAULT_SIZE, num_snakes, num_ladders, board_seed(master_seed))
+        }
+        None => Board::classic(),
+    };
+    let num_threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let histogram = run_parallel(master_seed, NUM_GAMES, num_threads, rng_backend, &die, &board);
 
-    for _ in 1..=1_000_000 {
-        num_rolls = snakes_and_ladders(&mut rng);
-    }
-    println!("Finished game in {} rolls", num_rolls);
+    // Thread count is just a scheduling detail, not part of the --seed
+    // contract: the histogram below is what's guaranteed reproducible.
+    println!("Played {} games across {} threads", NUM_GAMES, num_threads);
+    println!("{}", histogram.summary());
+}
+
+
+/// Look for a `--seed <u64>` pair in the command-line arguments.
+fn parse_seed_arg() -> Option<u64> {
+    let args: Vec<String> = std::env::args().collect();
+    let index = args.iter().position(|arg| arg == "--seed")?;
+    args.get(index + 1)?.parse().ok()
+}
+
+
+/// Look for a `--rng <smallrng|wyrand>` pair in the command-line
+/// arguments, selecting which `DiceSource` backend to benchmark.
+fn parse_rng_arg() -> Option<RngBackendKind> {
+    let args: Vec<String> = std::env::args().collect();
+    let index = args.iter().position(|arg| arg == "--rng")?;
+    RngBackendKind::parse(args.get(index + 1)?)
 }
 
 
-fn snakes_and_ladders(rng: &mut SmallRng) -> i32 {
+/// Look for a `--weights <w1,w2,w3,w4,w5,w6>` pair in the command-line
+/// arguments, one non-negative weight per face, to study a loaded die.
+///
+/// Returns `None` only when the flag is absent. A malformed value is a
+/// user mistake, not a silent fall-back to a fair die, so it's reported
+/// and the program exits.
+fn parse_weights_arg() -> Option<[u32; 6]> {
+    let args: Vec<String> = std::env::args().collect();
+    let index = args.iter().position(|arg| arg == "--weights")?;
+    let Some(value) = args.get(index + 1) else {
+        eprintln!("--weights requires a value, e.g. --weights 1,1,1,1,1,1");
+        std::process::exit(1);
+    };
+
+    let weights: Vec<u32> = value
+        .split(',')
+        .map(|w| {
+            w.parse().unwrap_or_else(|_| {
+                eprintln!("--weights: {:?} is not a valid non-negative face weight", w);
+                std::process::exit(1);
+            })
+        })
+        .collect();
+    let count = weights.len();
+
+    Some(weights.try_into().unwrap_or_else(|_| {
+        eprintln!("--weights needs exactly 6 comma-separated weights, got {}", count);
+        std::process::exit(1);
+    }))
+}
+
+
+/// Look for a `--random-board <num_snakes>,<num_ladders>` pair in the
+/// command-line arguments, to study a randomised layout instead of the
+/// classic board.
+fn parse_random_board_arg() -> Option<(usize, usize)> {
+    let args: Vec<String> = std::env::args().collect();
+    let index = args.iter().position(|arg| arg == "--random-board")?;
+    let (num_snakes, num_ladders) = args.get(index + 1)?.split_once(',')?;
+    Some((num_snakes.parse().ok()?, num_ladders.parse().ok()?))
+}
+
+
+/// Split `num_games` into `NUM_WORK_UNITS` fixed work-units, each with
+/// its own RNG backend, then schedule those units across `num_threads`
+/// threads and run them in parallel, returning the combined game-length
+/// histogram.
+///
+/// Every work-unit's generator is derived deterministically from
+/// `master_seed` and the work-unit's index, and the partition of games
+/// into units depends only on `num_games` (never on `num_threads`), so
+/// the aggregate results are identical for a given seed and game count
+/// no matter how many threads are used to produce them. `num_threads`
+/// only changes how the fixed units are divided up for scheduling.
+fn run_parallel(
+    master_seed: u64,
+    num_games: u64,
+    num_threads: usize,
+    rng_backend: RngBackendKind,
+    die: &WeightedDie,
+    board: &Board,
+) -> Histogram {
+    let work_units = split_work(num_games, NUM_WORK_UNITS);
+    let thread_ranges = split_indices(work_units.len(), num_threads);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = thread_ranges
+            .into_iter()
+            .map(|range| {
+                let work_units = &work_units;
+                scope.spawn(move || {
+                    let mut histogram = Histogram::new();
+                    for k in range {
+                        let mut rng = RngBackend::seeded(rng_backend, seed_for_work_unit(master_seed, k as u64));
+                        for _ in 0..work_units[k] {
+                            histogram.record(snakes_and_ladders(&mut rng, die, board));
+                        }
+                    }
+                    histogram
+                })
+            })
+            .collect();
+
+        let mut total = Histogram::new();
+        for handle in handles {
+            total.merge(&handle.join().unwrap());
+        }
+        total
+    })
+}
+
+
+/// Derive a work-unit's seed from the master seed and its index, so that
+/// splitting a run across N threads never changes the generators used.
+fn seed_for_work_unit(master_seed: u64, index: u64) -> u64 {
+    splitmix64(master_seed ^ splitmix64(index))
+}
+
+
+/// Derive the seed used to randomise a `--random-board` layout, so a
+/// randomised board is just as replayable from `--seed` as the game
+/// rolls are. Uses an index past the real work-unit range so it can
+/// never collide with one of their derived seeds.
+fn board_seed(master_seed: u64) -> u64 {
+    seed_for_work_unit(master_seed, NUM_WORK_UNITS as u64)
+}
+
+
+/// SplitMix64, used only to mix a `(master_seed, index)` pair into a
+/// well-distributed 64-bit seed. Not a cryptographic hash.
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+
+/// Divide `total` games as evenly as possible across `num_units` work-units.
+fn split_work(total: u64, num_units: usize) -> Vec<u64> {
+    let num_units = num_units.max(1) as u64;
+    let base = total / num_units;
+    let remainder = total % num_units;
+
+    (0..num_units)
+        .map(|k| if k < remainder { base + 1 } else { base })
+        .collect()
+}
+
+
+/// Divide the index range `0..len` as evenly as possible into
+/// `num_parts` contiguous ranges, for scheduling a fixed set of
+/// work-units across however many threads are available.
+fn split_indices(len: usize, num_parts: usize) -> Vec<std::ops::Range<usize>> {
+    let num_parts = num_parts.max(1);
+    let base = len / num_parts;
+    let remainder = len % num_parts;
+
+    let mut start = 0;
+    (0..num_parts)
+        .map(|i| {
+            let size = if i < remainder { base + 1 } else { base };
+            let range = start..start + size;
+            start += size;
+            range
+        })
+        .collect()
+}
+
+
+fn snakes_and_ladders(rng: &mut impl DiceSource, die: &WeightedDie, board: &Board) -> i32 {
     let mut num_rolls = 0;
     let mut place = 0;
 
     loop {
         // Roll the dice
-        //~ let roll = rng.gen_range(1..=6);            // 227ms for 1e6 games
-        let roll = rng.next_u64() % 6 + 1;              // 176ms for 1e6 games
+        let roll = die.roll(rng);
         num_rolls += 1;
 
         // Where did you end up?
         let landed = place + roll;
 
         // Where did you *really* end up?
-        place = match landed {
-            // Ladders
-            1 => 38,
-            4 => 14,
-            9 => 31,
-            21 => 42,
-            28 => 84,
-            36 => 44,
-            51 => 67,
-            71 => 91,
-            80 => 100,
-
-            // Snakes
-            98 => 78,
-            95 => 75,
-            93 => 73,
-            87 => 24,
-            64 => 60,
-            62 => 19,
-            56 => 53,
-            49 => 11,
-            48 => 26,
-            16 => 6,
-
+        place = if landed > board.size() {
             // Too high? Stay where you are.
-            n if n > 100 => place,
-
-            // Normal move
-            _ => landed,
+            place
+        } else {
+            board.resolve(landed)
         };
 
-        if place == 100 { break; }
+        if place == board.size() || num_rolls >= MAX_ROLLS_PER_GAME { break; }
     };
 
     num_rolls
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A given seed and game count must produce an identical histogram
+    /// summary no matter how many threads ran the simulation; this pins
+    /// that down.
+    #[test]
+    fn same_seed_reproduces_identical_results_across_thread_counts() {
+        let die = WeightedDie::fair();
+        let board = Board::classic();
+        let seed = 123_456_789;
+        let num_games = 5_000;
+
+        let one_thread = run_parallel(seed, num_games, 1, RngBackendKind::SmallRng, &die, &board).summary();
+        let many_threads = run_parallel(seed, num_games, 8, RngBackendKind::SmallRng, &die, &board).summary();
+
+        assert_eq!(one_thread, many_threads);
+    }
+
+    /// A die that can only overshoot the finish from some reachable
+    /// square makes a game unwinnable; the roll cap must stop it rather
+    /// than loop forever.
+    #[test]
+    fn unwinnable_die_is_capped_instead_of_hanging() {
+        let die = WeightedDie::new([0, 0, 0, 0, 0, 1]); // always rolls a 6
+        let board = Board::classic();
+        let mut rng = SmallRng::seed_from_u64(1);
+
+        let num_rolls = snakes_and_ladders(&mut rng, &die, &board);
+
+        assert_eq!(num_rolls, MAX_ROLLS_PER_GAME);
+    }
+}
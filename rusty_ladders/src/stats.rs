@@ -0,0 +1,178 @@
+//! Aggregate statistics over the roll-count of many played games.
+
+/// Highest roll count tracked exactly in the histogram; games that take
+/// longer than this land in the overflow bucket instead of growing the
+/// vector.
+const MAX_TRACKED_ROLLS: usize = 500;
+
+
+/// A histogram of game lengths (in dice rolls), with a single overflow
+/// bucket for the rare game that runs longer than we bothered to track.
+pub struct Histogram {
+    buckets: Vec<u64>,
+    overflow: u64,
+    min: i32,
+    max: i32,
+    count: u64,
+    sum: u64,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Histogram {
+            buckets: vec![0; MAX_TRACKED_ROLLS + 1],
+            overflow: 0,
+            min: i32::MAX,
+            max: i32::MIN,
+            count: 0,
+            sum: 0,
+        }
+    }
+
+    /// Record one game's roll count. Allocation-free.
+    pub fn record(&mut self, num_rolls: i32) {
+        match self.buckets.get_mut(num_rolls as usize) {
+            Some(bucket) => *bucket += 1,
+            None => self.overflow += 1,
+        }
+        self.min = self.min.min(num_rolls);
+        self.max = self.max.max(num_rolls);
+        self.count += 1;
+        self.sum += num_rolls as u64;
+    }
+
+    /// Fold another histogram's counts into this one, e.g. to combine
+    /// per-thread results into a single report.
+    pub fn merge(&mut self, other: &Histogram) {
+        for (bucket, &count) in self.buckets.iter_mut().zip(other.buckets.iter()) {
+            *bucket += count;
+        }
+        self.overflow += other.overflow;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.count += other.count;
+        self.sum += other.sum;
+    }
+
+    pub fn summary(&self) -> Summary {
+        Summary {
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            mean: self.sum as f64 / self.count as f64,
+            median: self.percentile(0.50),
+            std_dev: self.std_dev(),
+            p90: self.percentile(0.90),
+            p99: self.percentile(0.99),
+        }
+    }
+
+    /// Estimate the roll count at or below which `fraction` of games finished,
+    /// by walking the histogram buckets in order. Overflowed games are
+    /// treated as finishing just past `MAX_TRACKED_ROLLS`.
+    fn percentile(&self, fraction: f64) -> i32 {
+        let target = (fraction * self.count as f64).ceil() as u64;
+        let mut cumulative = 0;
+        for (rolls, &count) in self.buckets.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return rolls as i32;
+            }
+        }
+        (MAX_TRACKED_ROLLS + 1) as i32
+    }
+
+    fn std_dev(&self) -> f64 {
+        let mean = self.sum as f64 / self.count as f64;
+        let mut variance_sum = 0.0;
+        for (rolls, &count) in self.buckets.iter().enumerate() {
+            let diff = rolls as f64 - mean;
+            variance_sum += diff * diff * count as f64;
+        }
+        // Overflowed games are rare outliers; approximate them at the
+        // overflow boundary rather than dropping them from the variance.
+        let diff = (MAX_TRACKED_ROLLS + 1) as f64 - mean;
+        variance_sum += diff * diff * self.overflow as f64;
+        (variance_sum / self.count as f64).sqrt()
+    }
+}
+
+
+/// Summary statistics produced from a [`Histogram`].
+#[derive(Debug, PartialEq)]
+pub struct Summary {
+    pub count: u64,
+    pub min: i32,
+    pub max: i32,
+    pub mean: f64,
+    pub median: i32,
+    pub std_dev: f64,
+    pub p90: i32,
+    pub p99: i32,
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "games: {}, min: {}, max: {}, mean: {:.2}, median: {}, std dev: {:.2}, p90: {}, p99: {}",
+            self.count, self.min, self.max, self.mean, self.median, self.std_dev, self.p90, self.p99,
+        )
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarises_a_known_distribution() {
+        let mut histogram = Histogram::new();
+        for rolls in 1..=10 {
+            histogram.record(rolls);
+        }
+        let summary = histogram.summary();
+
+        assert_eq!(summary.count, 10);
+        assert_eq!(summary.min, 1);
+        assert_eq!(summary.max, 10);
+        assert!((summary.mean - 5.5).abs() < 1e-9);
+        assert_eq!(summary.median, 5);
+        assert_eq!(summary.p90, 9);
+        assert_eq!(summary.p99, 10);
+        assert!((summary.std_dev - 8.25_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn merge_matches_recording_into_a_single_histogram() {
+        let mut combined = Histogram::new();
+        let mut first = Histogram::new();
+        let mut second = Histogram::new();
+
+        for rolls in [3, 7, 7, 12, 20] {
+            combined.record(rolls);
+        }
+        for rolls in [3, 7] {
+            first.record(rolls);
+        }
+        for rolls in [7, 12, 20] {
+            second.record(rolls);
+        }
+        first.merge(&second);
+
+        assert_eq!(first.summary().count, combined.summary().count);
+        assert_eq!(first.summary().mean, combined.summary().mean);
+        assert_eq!(first.summary().median, combined.summary().median);
+    }
+
+    #[test]
+    fn overflow_bucket_catches_rolls_past_the_tracked_range() {
+        let mut histogram = Histogram::new();
+        histogram.record((MAX_TRACKED_ROLLS + 50) as i32);
+        let summary = histogram.summary();
+
+        assert_eq!(summary.count, 1);
+        assert_eq!(summary.max, (MAX_TRACKED_ROLLS + 50) as i32);
+    }
+}